@@ -0,0 +1,294 @@
+//! Optional mesh loading from common file formats (STL, OBJ) into the vertex/face
+//! representation that [`crate::UniformSurface::new`] and [`crate::PoissonDiskSurface::new`]
+//! consume.
+
+use crate::vecmath as m;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IoError {
+    #[error("failed to read {path}: {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse {path}: {reason}")]
+    Parse { path: String, reason: String },
+}
+
+/// Loads a binary or ASCII STL file into a shared vertex/face buffer.
+///
+/// STL stores an unindexed position per triangle vertex, so coincident vertices are
+/// deduplicated by hashing quantized coordinates to rebuild the shared index buffer that
+/// [`crate::PoissonDiskSurface`]'s edge adjacency depends on.
+pub fn load_stl<P: AsRef<Path>>(path: P) -> Result<(Vec<m::Vector>, Vec<[usize; 3]>), IoError> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).map_err(|source| IoError::Read {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let triangles = if is_binary_stl(&bytes) {
+        parse_binary_stl(&bytes)
+    } else {
+        parse_ascii_stl(&bytes, path)?
+    };
+    Ok(weld_vertices(triangles))
+}
+
+/// Loads a Wavefront OBJ file's `v` positions and `f` faces, triangulating polygon faces
+/// via a fan from their first vertex.
+pub fn load_obj<P: AsRef<Path>>(path: P) -> Result<(Vec<m::Vector>, Vec<[usize; 3]>), IoError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path).map_err(|source| IoError::Read {
+        path: path.display().to_string(),
+        source,
+    })?;
+    let parse_err = |reason: String| IoError::Parse {
+        path: path.display().to_string(),
+        reason,
+    };
+
+    let mut verts = Vec::new();
+    let mut faces = Vec::new();
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let coords: Vec<f32> = tokens
+                    .take(3)
+                    .map(|t| t.parse::<f32>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| parse_err(format!("invalid vertex line: {line}")))?;
+                let &[x, y, z] = coords.as_slice() else {
+                    return Err(parse_err(format!(
+                        "vertex line did not have 3 coordinates: {line}"
+                    )));
+                };
+                verts.push([x, y, z]);
+            }
+            Some("f") => {
+                let indices: Vec<usize> = tokens
+                    .map(|t| parse_obj_index(t, verts.len()))
+                    .collect::<Result<_, _>>()
+                    .map_err(parse_err)?;
+                if indices.len() < 3 {
+                    return Err(parse_err(format!(
+                        "face has fewer than 3 verticies: {line}"
+                    )));
+                }
+                for i in 1..indices.len() - 1 {
+                    faces.push([indices[0], indices[i], indices[i + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok((verts, faces))
+}
+
+fn parse_obj_index(token: &str, vert_count: usize) -> Result<usize, String> {
+    let vertex_part = token.split('/').next().unwrap_or(token);
+    let raw: isize = vertex_part
+        .parse()
+        .map_err(|_| format!("invalid face index {token}"))?;
+    match raw.cmp(&0) {
+        std::cmp::Ordering::Greater => Ok(raw as usize - 1),
+        std::cmp::Ordering::Less => vert_count
+            .checked_sub((-raw) as usize)
+            .ok_or_else(|| format!("negative face index {token} out of range")),
+        std::cmp::Ordering::Equal => Err(format!("face index {token} cannot be 0")),
+    }
+}
+
+/// Binary STL has a fixed-size 80 byte header + 4 byte triangle count, followed by exactly
+/// 50 bytes per triangle; ASCII STL doesn't, so a byte-length match is a reliable test even
+/// though some binary exporters (incorrectly) start their header with `b"solid"`.
+fn is_binary_stl(bytes: &[u8]) -> bool {
+    let Some(header) = bytes.get(80..84) else {
+        return false;
+    };
+    let triangle_count = u32::from_le_bytes(header.try_into().unwrap()) as usize;
+    bytes.len() == 84 + triangle_count * 50
+}
+
+fn parse_binary_stl(bytes: &[u8]) -> Vec<[m::Vector; 3]> {
+    let triangle_count = u32::from_le_bytes(bytes[80..84].try_into().unwrap()) as usize;
+    let read_vec = |tri: &[u8], offset: usize| -> m::Vector {
+        [
+            f32::from_le_bytes(tri[offset..offset + 4].try_into().unwrap()),
+            f32::from_le_bytes(tri[offset + 4..offset + 8].try_into().unwrap()),
+            f32::from_le_bytes(tri[offset + 8..offset + 12].try_into().unwrap()),
+        ]
+    };
+    (0..triangle_count)
+        .map(|i| {
+            let offset = 84 + i * 50;
+            let tri = &bytes[offset..offset + 50];
+            // tri[0..12] holds the facet normal, which is skipped: face normals are derived
+            // from the positions when a surface is built from them.
+            [read_vec(tri, 12), read_vec(tri, 24), read_vec(tri, 36)]
+        })
+        .collect()
+}
+
+fn parse_ascii_stl(bytes: &[u8], path: &Path) -> Result<Vec<[m::Vector; 3]>, IoError> {
+    let parse_err = |reason: String| IoError::Parse {
+        path: path.display().to_string(),
+        reason,
+    };
+    let text = std::str::from_utf8(bytes)
+        .map_err(|_| parse_err("not valid UTF-8 ASCII STL".to_string()))?;
+
+    let mut triangles = Vec::new();
+    let mut facet_verts = Vec::new();
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("vertex") => {
+                let coords: Vec<f32> = tokens
+                    .map(|t| t.parse::<f32>())
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| parse_err(format!("invalid vertex coordinate: {line}")))?;
+                let &[x, y, z] = coords.as_slice() else {
+                    return Err(parse_err(format!(
+                        "vertex line did not have 3 coordinates: {line}"
+                    )));
+                };
+                facet_verts.push([x, y, z]);
+            }
+            Some("endfacet") => {
+                let &[p1, p2, p3] = facet_verts.as_slice() else {
+                    return Err(parse_err("facet did not have exactly 3 verticies".to_string()));
+                };
+                triangles.push([p1, p2, p3]);
+                facet_verts.clear();
+            }
+            _ => {}
+        }
+    }
+    Ok(triangles)
+}
+
+/// Rebuilds a shared vertex/face index buffer from STL's unindexed per-triangle
+/// positions by hashing coordinates quantized to 1e-5 units.
+fn weld_vertices(triangles: Vec<[m::Vector; 3]>) -> (Vec<m::Vector>, Vec<[usize; 3]>) {
+    const QUANTIZATION: f32 = 1e5;
+    let quantize = |[x, y, z]: m::Vector| -> [i64; 3] {
+        [
+            (x * QUANTIZATION).round() as i64,
+            (y * QUANTIZATION).round() as i64,
+            (z * QUANTIZATION).round() as i64,
+        ]
+    };
+
+    let mut verts = Vec::new();
+    let mut vert_index = HashMap::new();
+    let mut faces = Vec::with_capacity(triangles.len());
+    for triangle in triangles {
+        let face = triangle.map(|p| {
+            *vert_index.entry(quantize(p)).or_insert_with(|| {
+                verts.push(p);
+                verts.len() - 1
+            })
+        });
+        faces.push(face);
+    }
+    (verts, faces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASCII_STL_TETRAHEDRON: &str = "solid tetra
+facet normal 0 0 -1
+  outer loop
+    vertex 0 0 0
+    vertex 0 1 0
+    vertex 1 0 0
+  endloop
+endfacet
+facet normal 0 -1 0
+  outer loop
+    vertex 0 0 0
+    vertex 1 0 0
+    vertex 0 0 1
+  endloop
+endfacet
+facet normal -1 0 0
+  outer loop
+    vertex 0 0 0
+    vertex 0 0 1
+    vertex 0 1 0
+  endloop
+endfacet
+endsolid tetra
+";
+
+    const OBJ_TETRAHEDRON: &str = "v 0 0 0
+v 0 1 0
+v 1 0 0
+v 0 0 1
+f 1 2 3
+f 1 3 4
+f 1 4 2
+";
+
+    fn write_temp(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, contents).expect("failed to write test fixture");
+        path
+    }
+
+    #[test]
+    fn load_stl_round_trips_ascii_fixture() {
+        let path = write_temp("mesh_rand_test_tetra.stl", ASCII_STL_TETRAHEDRON);
+        let (verts, faces) = load_stl(&path).expect("failed to load STL fixture");
+        fs::remove_file(&path).ok();
+
+        // 9 positions across 3 facets that all share the origin (and pairwise an edge)
+        // should weld down to 4 distinct verticies.
+        assert_eq!(verts.len(), 4);
+        assert_eq!(faces.len(), 3);
+        for &[i, j, k] in &faces {
+            assert!(i < verts.len() && j < verts.len() && k < verts.len());
+        }
+    }
+
+    #[test]
+    fn load_obj_round_trips_fixture() {
+        let path = write_temp("mesh_rand_test_tetra.obj", OBJ_TETRAHEDRON);
+        let (verts, faces) = load_obj(&path).expect("failed to load OBJ fixture");
+        fs::remove_file(&path).ok();
+
+        assert_eq!(
+            verts,
+            vec![
+                [0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0],
+            ]
+        );
+        assert_eq!(faces, vec![[0, 1, 2], [0, 2, 3], [0, 3, 1]]);
+    }
+
+    #[test]
+    fn weld_vertices_dedups_coincident_positions() {
+        let triangles = vec![
+            [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            [[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        ];
+        let (verts, faces) = weld_vertices(triangles);
+
+        // 6 input positions, but the origin and the [0, 1, 0] vertex are shared, so only
+        // 4 distinct verticies should survive.
+        assert_eq!(verts.len(), 4);
+        assert_eq!(faces.len(), 2);
+        assert_eq!(faces[0][0], faces[1][0]);
+    }
+}
@@ -14,7 +14,7 @@
 //! ];
 //! // Faces, oriented to be pointing outwards:
 //! let faces = [[1, 0, 2], [2, 0, 3], [0, 1, 3], [1, 2, 3]];
-//! let mesh_dist = UniformSurface::new(&verticies, &faces)?;
+//! let mesh_dist = UniformSurface::new(&verticies, &faces, None, None)?;
 //! let mut rng = rand::thread_rng();
 //! let SurfSample { position, .. } = mesh_dist.sample(&mut rng);
 //! println!("generated point on mesh at {position:?}");
@@ -22,7 +22,9 @@
 //! # }
 //! ```
 mod errors;
+pub mod io;
 mod mesh;
+mod spatial_grid;
 mod surface;
 mod vecmath;
 pub use surface::poisson_disk::PoissonDiskSurface;
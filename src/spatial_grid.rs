@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use crate::vecmath as m;
+
+/// A uniform 3D background grid used to accelerate nearest-neighbor rejection tests:
+/// points are bucketed by `floor(position / cell_size)`, so candidates only need to check
+/// the 27 neighboring cells instead of every previously accepted point.
+pub(crate) struct SpatialGrid<T> {
+    cell_size: f32,
+    cells: HashMap<[i32; 3], Vec<(m::Vector, T)>>,
+}
+
+impl<T: Copy> SpatialGrid<T> {
+    pub(crate) fn new(cell_size: f32) -> Self {
+        SpatialGrid {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell_of(&self, [x, y, z]: m::Vector) -> [i32; 3] {
+        [
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+            (z / self.cell_size).floor() as i32,
+        ]
+    }
+
+    pub(crate) fn insert(&mut self, position: m::Vector, value: T) {
+        self.cells
+            .entry(self.cell_of(position))
+            .or_default()
+            .push((position, value));
+    }
+
+    /// Returns every `(position, value)` stored in the 27 cells surrounding `position`'s
+    /// own cell (itself included).
+    pub(crate) fn neighbors(&self, position: m::Vector) -> Vec<(m::Vector, T)> {
+        let [cx, cy, cz] = self.cell_of(position);
+        let mut found = Vec::new();
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    if let Some(cell) = self.cells.get(&[cx + dx, cy + dy, cz + dz]) {
+                        found.extend(cell.iter().copied());
+                    }
+                }
+            }
+        }
+        found
+    }
+}
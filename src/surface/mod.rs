@@ -3,13 +3,8 @@ pub mod uniform;
 
 use crate::vecmath as m;
 use rand_distr::Distribution;
-use thiserror::Error;
 
-#[derive(Error, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub enum MeshRandError {
-    #[error("failed to initialize: {0}")]
-    Initialization(String),
-}
+pub use crate::errors::MeshRandError;
 
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
 pub struct Triangle {
@@ -28,10 +23,26 @@ pub struct Triangle {
     origin: m::Vector,
     u: m::Vector,
     v: m::Vector,
+    // Per-vertex normals (in `points` order), interpolated into a shading normal at
+    // sample time. `None` when the surface was built without vertex normals.
+    vertex_normals: Option<[m::Vector; 3]>,
+    // Per-vertex UVs (in `points` order), interpolated into a texture coordinate at
+    // sample time. `None` when the surface was built without UVs.
+    vertex_uvs: Option<[[f32; 2]; 3]>,
+    // Tangent direction for the triangle, derived from the UV gradient when `vertex_uvs`
+    // is set (not unit length), or the triangle's longest edge otherwise (normalized). Orthogonalized
+    // against the sample's shading normal (and normalized) at sample time.
+    tangent: m::Vector,
 }
 
 impl Triangle {
-    pub fn from_points(p1: m::Vector, p2: m::Vector, p3: m::Vector) -> Result<Self, MeshRandError> {
+    pub fn from_points(
+        p1: m::Vector,
+        p2: m::Vector,
+        p3: m::Vector,
+        vertex_normals: Option<[m::Vector; 3]>,
+        vertex_uvs: Option<[[f32; 2]; 3]>,
+    ) -> Result<Self, MeshRandError> {
         let origin = p1;
         let u = m::diff(p2, p1);
         let v = m::diff(p3, p1);
@@ -44,6 +55,11 @@ impl Triangle {
             ));
         }
         let normal = m::div(normal_dir, len);
+        let tangent = match vertex_uvs {
+            Some(uvs) => tangent_from_uv_gradient(u, v, uvs)
+                .unwrap_or_else(|| longest_edge_tangent([p1, p2, p3])),
+            None => longest_edge_tangent([p1, p2, p3]),
+        };
         Ok(Triangle {
             points: [p1, p2, p3],
             origin,
@@ -51,6 +67,9 @@ impl Triangle {
             v,
             normal,
             area,
+            vertex_normals,
+            vertex_uvs,
+            tangent,
         })
     }
 
@@ -62,21 +81,107 @@ impl Triangle {
             .iter()
             .any(|&p| m::dist_sq(position, p) <= r * r)
     }
-}
 
-impl Distribution<m::Vector> for Triangle {
-    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> m::Vector {
+    /// Draws a uniformly distributed point within the triangle, returning it together with
+    /// the barycentric weights `[w0, w1, w2]` (one per vertex in `points` order) used to
+    /// place it.
+    fn sample_barycentric<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> (m::Vector, [f32; 3]) {
         let mut v_rand = rng.gen_range(0.0..1.0);
         let mut u_rand = rng.gen_range(0.0..1.0);
         if v_rand + u_rand > 1.0 {
             v_rand = 1.0 - v_rand;
             u_rand = 1.0 - u_rand;
         }
-        m::add(
+        let point = m::add(
             self.origin,
             m::add(m::mul(self.v, v_rand), m::mul(self.u, u_rand)),
-        )
+        );
+        (point, [1.0 - u_rand - v_rand, u_rand, v_rand])
+    }
+
+    /// Draws a [SurfSample] from within the triangle, interpolating any supplied per-vertex
+    /// normals/UVs at the sampled barycentric coordinates.
+    pub(crate) fn sample_surf<R: rand::Rng + ?Sized>(
+        &self,
+        t_index: usize,
+        rng: &mut R,
+    ) -> SurfSample {
+        let (position, barycentric) = self.sample_barycentric(rng);
+        let normal = self
+            .vertex_normals
+            .map(|normals| m::normalize(interpolate_vec3(normals, barycentric)));
+        let uv = self
+            .vertex_uvs
+            .map(|uvs| interpolate_uv(uvs, barycentric));
+        let shading_normal = normal.unwrap_or(self.normal);
+        let tangent = m::normalize(m::diff(
+            self.tangent,
+            m::mul(shading_normal, m::dot(shading_normal, self.tangent)),
+        ));
+        let bitangent = m::cross(shading_normal, tangent);
+        SurfSample {
+            position,
+            barycentric,
+            normal,
+            uv,
+            tangent,
+            bitangent,
+            triangle: *self,
+            t_index,
+        }
+    }
+}
+
+impl Distribution<m::Vector> for Triangle {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> m::Vector {
+        self.sample_barycentric(rng).0
+    }
+}
+
+fn interpolate_vec3(attrs: [m::Vector; 3], [w0, w1, w2]: [f32; 3]) -> m::Vector {
+    m::add(
+        m::add(m::mul(attrs[0], w0), m::mul(attrs[1], w1)),
+        m::mul(attrs[2], w2),
+    )
+}
+
+fn interpolate_uv([a0, a1, a2]: [[f32; 2]; 3], [w0, w1, w2]: [f32; 3]) -> [f32; 2] {
+    [
+        a0[0] * w0 + a1[0] * w1 + a2[0] * w2,
+        a0[1] * w0 + a1[1] * w1 + a2[1] * w2,
+    ]
+}
+
+/// Solves for the tangent direction from the triangle's edges and UV gradient, the
+/// standard edge/UV-delta solve: given edges `e1 = p2 - p1`, `e2 = p3 - p1` and UV deltas
+/// `duv1`, `duv2`, `tangent = (e1*duv2.y - e2*duv1.y) / (duv1.x*duv2.y - duv1.y*duv2.x)`.
+/// Returns `None` when the UV triangle is degenerate (zero UV area).
+fn tangent_from_uv_gradient(e1: m::Vector, e2: m::Vector, uvs: [[f32; 2]; 3]) -> Option<m::Vector> {
+    let duv1 = [uvs[1][0] - uvs[0][0], uvs[1][1] - uvs[0][1]];
+    let duv2 = [uvs[2][0] - uvs[0][0], uvs[2][1] - uvs[0][1]];
+    let denom = duv1[0] * duv2[1] - duv1[1] * duv2[0];
+    if denom.abs() <= f32::EPSILON {
+        return None;
     }
+    Some(m::div(
+        m::diff(m::mul(e1, duv2[1]), m::mul(e2, duv1[1])),
+        denom,
+    ))
+}
+
+/// Falls back to an arbitrary tangent along the triangle's longest edge, for triangles
+/// with no UVs (or a degenerate UV gradient).
+fn longest_edge_tangent(points: [m::Vector; 3]) -> m::Vector {
+    let edges = [
+        m::diff(points[1], points[0]),
+        m::diff(points[2], points[1]),
+        m::diff(points[0], points[2]),
+    ];
+    let longest = edges
+        .into_iter()
+        .max_by(|a, b| m::len_sq(*a).total_cmp(&m::len_sq(*b)))
+        .expect("3 edges is never empty");
+    m::normalize(longest)
 }
 
 /// Surface sample returned from surface distributions
@@ -84,6 +189,24 @@ impl Distribution<m::Vector> for Triangle {
 pub struct SurfSample {
     /// Generated point on the model surface
     pub position: m::Vector,
+    /// Barycentric weights `[w0, w1, w2]` of [position](Self::position) within
+    /// [triangle](Self::triangle), one per vertex in `triangle.points` order.
+    pub barycentric: [f32; 3],
+    /// Smooth shading normal interpolated from per-vertex normals, if the surface was
+    /// built with any (see `UniformSurface::new`). Falls back to `None` otherwise, in
+    /// which case [triangle.normal](Triangle::normal) is the flat face normal.
+    pub normal: Option<m::Vector>,
+    /// Texture coordinate interpolated from per-vertex UVs, if the surface was built with
+    /// any (see `UniformSurface::new`).
+    pub uv: Option<[f32; 2]>,
+    /// Tangent of the surface at [position](Self::position), orthonormal to
+    /// [normal](Self::normal) (or [triangle.normal](Triangle::normal) when no per-vertex
+    /// normals were supplied). Derived from the triangle's UV gradient when UVs are
+    /// available, or an arbitrary direction along its longest edge otherwise.
+    pub tangent: m::Vector,
+    /// Bitangent completing the `(tangent, bitangent, normal)` right-handed frame, for
+    /// building a rotation matrix to orient scattered geometry.
+    pub bitangent: m::Vector,
     /// Triangle the point is contained in
     pub triangle: Triangle,
     // Index of the triangle the point resides in, in the face slice used for initialization
@@ -92,20 +215,57 @@ pub struct SurfSample {
 
 //utility functions:
 
-fn vert_ids_to_pos(
-    &[i, j, k]: &[usize; 3],
-    verts: &[m::Vector],
-) -> Result<[m::Vector; 3], MeshRandError> {
+fn vert_ids_to<T: Copy>(&[i, j, k]: &[usize; 3], data: &[T]) -> Result<[T; 3], MeshRandError> {
     let ind_err = |i| {
         MeshRandError::Initialization(format!(
             "face referenced vert index {} which is out of range (vert.len() = {})",
             i,
-            verts.len()
+            data.len()
         ))
     };
     Ok([
-        *verts.get(i).ok_or_else(|| ind_err(i))?,
-        *verts.get(j).ok_or_else(|| ind_err(j))?,
-        *verts.get(k).ok_or_else(|| ind_err(k))?,
+        *data.get(i).ok_or_else(|| ind_err(i))?,
+        *data.get(j).ok_or_else(|| ind_err(j))?,
+        *data.get(k).ok_or_else(|| ind_err(k))?,
     ])
 }
+
+fn vert_ids_to_pos(
+    ids: &[usize; 3],
+    verts: &[m::Vector],
+) -> Result<[m::Vector; 3], MeshRandError> {
+    vert_ids_to(ids, verts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn sample_surf_produces_orthonormal_tangent_frame() {
+        let triangle = Triangle::from_points(
+            [0.0, 0.0, 0.0],
+            [2.0, 0.0, 0.0],
+            [0.0, 3.0, 0.0],
+            None,
+            Some([[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]),
+        )
+        .unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+
+        for _ in 0..20 {
+            let sample = triangle.sample_surf(0, &mut rng);
+            let normal = sample.normal.unwrap_or(triangle.normal);
+
+            assert!((m::len(sample.tangent) - 1.0).abs() < 1e-4);
+            assert!((m::len(sample.bitangent) - 1.0).abs() < 1e-4);
+            assert!(m::dot(sample.tangent, normal).abs() < 1e-4);
+            assert!(m::dot(sample.bitangent, normal).abs() < 1e-4);
+            assert!(m::dot(sample.tangent, sample.bitangent).abs() < 1e-4);
+
+            let [w0, w1, w2] = sample.barycentric;
+            assert!((w0 + w1 + w2 - 1.0).abs() < 1e-4);
+        }
+    }
+}
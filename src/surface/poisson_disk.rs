@@ -1,12 +1,15 @@
+use std::collections::BinaryHeap;
+
 use rand_distr::Distribution;
 
 use super::uniform::UniformSurface;
 use crate::errors::MeshRandError;
 use crate::mesh::SpaceQueryMesh;
+use crate::spatial_grid::SpatialGrid;
 use crate::{vecmath as m, SurfSample};
 
 pub struct PoissonDiskSurface {
-    mesh: SpaceQueryMesh,
+    mesh: Option<SpaceQueryMesh>,
     sampler: UniformSurface,
     r: f32,
 }
@@ -15,18 +18,61 @@ impl PoissonDiskSurface {
     pub fn new(r: f32, verts: &[m::Vector], faces: &[[usize; 3]]) -> Result<Self, MeshRandError> {
         let tri_mesh_graph = SpaceQueryMesh::new(r, verts, faces)?;
         println!("trimesh constructed");
-        let sampler = UniformSurface::new(&tri_mesh_graph.verticies, &tri_mesh_graph.faces)?;
+        let sampler =
+            UniformSurface::new(&tri_mesh_graph.verticies, &tri_mesh_graph.faces, None, None)?;
         Ok(Self {
-            mesh: tri_mesh_graph,
+            mesh: Some(tri_mesh_graph),
             sampler,
             r,
         })
     }
 
-    pub fn sample_naive<R>(&self, retries: u32, max: u32, rng: &mut R) -> Vec<m::Vector>
+    /// Initializes a Poisson-disk surface sampler backed by a 3D uniform background grid
+    /// instead of mesh subdivision and BFS triangle adjacency.
+    ///
+    /// Candidates are still drawn via area-proportional triangle sampling
+    /// ([`UniformSurface`]), but rejection tests against [`sample_grid`](Self::sample_grid)
+    /// are accelerated by a uniform grid of cells sized `r`: accepted points are stored by
+    /// cell, and a new candidate only needs to check its 27 neighboring cells for a
+    /// conflict, since any two points in non-adjacent cells are guaranteed to be at least
+    /// `r` apart. This makes each rejection test O(1) and removes the need to subdivide
+    /// the mesh at all.
+    pub fn new_grid(
+        r: f32,
+        verts: &[m::Vector],
+        faces: &[[usize; 3]],
+    ) -> Result<Self, MeshRandError> {
+        let sampler = UniformSurface::new(verts, faces, None, None)?;
+        Ok(Self {
+            mesh: None,
+            sampler,
+            r,
+        })
+    }
+
+    /// Rejection-samples the surface via the subdivided mesh + BFS adjacency approach.
+    ///
+    /// # Errors
+    /// Returns an error if the sampler was built with [`new_grid`](Self::new_grid) rather
+    /// than [`new`](Self::new), since this method relies on the subdivided mesh that only
+    /// `new` builds. Prefer [`sample_grid`](Self::sample_grid) on large meshes, where the
+    /// background grid avoids the cost of subdivision entirely.
+    pub fn sample_naive<R>(
+        &self,
+        retries: u32,
+        max: u32,
+        rng: &mut R,
+    ) -> Result<Vec<m::Vector>, MeshRandError>
     where
         R: rand::Rng + ?Sized,
     {
+        let mesh = self.mesh.as_ref().ok_or_else(|| {
+            MeshRandError::Initialization(
+                "sample_naive requires a sampler built with PoissonDiskSurface::new, not new_grid"
+                    .to_string(),
+            )
+        })?;
+
         let tri_count = self.sampler.triangles.len();
         let mut tri_buckets = vec![Vec::new(); tri_count];
 
@@ -39,7 +85,7 @@ impl PoissonDiskSurface {
                 ..
             } = self.sampler.sample(rng);
             let exists_closer =
-                self.exists_point_within_sphere(self.r, position, t_root, &tri_buckets);
+                self.exists_point_within_sphere(mesh, self.r, position, t_root, &tri_buckets);
             if !exists_closer {
                 tri_buckets[t_root].push(position);
                 count += 1;
@@ -48,11 +94,12 @@ impl PoissonDiskSurface {
             }
         }
 
-        tri_buckets.concat()
+        Ok(tri_buckets.concat())
     }
 
     fn exists_point_within_sphere(
         &self,
+        mesh: &SpaceQueryMesh,
         r: f32,
         position: [f32; 3],
         t_index: usize,
@@ -64,7 +111,7 @@ impl PoissonDiskSurface {
             let tri = self.sampler.triangles[tri_ind];
             let intersects = tri.intersects_sphere(position, r);
             if intersects {
-                for next_ind in self.mesh.neighbors(tri_ind) {
+                for next_ind in mesh.neighbors(tri_ind) {
                     if !visited.contains(&next_ind) {
                         visited.push(next_ind);
                         searching.push(next_ind);
@@ -81,4 +128,224 @@ impl PoissonDiskSurface {
         }
         false
     }
+
+    /// Rejection-samples the surface using a 3D uniform background grid, checking only the
+    /// 27 neighboring cells of a candidate instead of walking triangle adjacency.
+    ///
+    /// Candidates are drawn directly from the [`UniformSurface`] and no triangle adjacency
+    /// is needed, so this works with a sampler built via either [`new`](Self::new) (whose
+    /// surface is the subdivided mesh) or [`new_grid`](Self::new_grid) (the mesh as given).
+    pub fn sample_grid<R>(&self, retries: u32, max: u32, rng: &mut R) -> Vec<m::Vector>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        let mut grid: SpatialGrid<()> = SpatialGrid::new(self.r);
+        let mut accepted = Vec::new();
+        let mut count = 0;
+        let mut failures = 0;
+        while retries > failures && count < max {
+            let SurfSample { position, .. } = self.sampler.sample(rng);
+            let exists_closer = grid
+                .neighbors(position)
+                .iter()
+                .any(|&(p, ())| m::dist_sq(p, position) < self.r * self.r);
+            if !exists_closer {
+                grid.insert(position, ());
+                accepted.push(position);
+                count += 1;
+            } else {
+                failures += 1;
+            }
+        }
+        accepted
+    }
+
+    /// Produces `target_n` blue-noise distributed points via weighted sample elimination
+    /// (Yuksel 2015).
+    ///
+    /// Draws `oversample_factor * target_n` uniform candidates from [`UniformSurface`],
+    /// then greedily eliminates the candidate with the highest weight (the one most
+    /// crowded by its neighbors) until only `target_n` survive. This avoids the
+    /// dart-throwing retry stalls of [`sample_naive`](Self::sample_naive) /
+    /// [`sample_grid`](Self::sample_grid) at the cost of drawing more candidates up front.
+    pub fn sample_eliminated<R>(
+        &self,
+        target_n: usize,
+        oversample_factor: usize,
+        rng: &mut R,
+    ) -> Vec<m::Vector>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        let candidate_count = target_n * oversample_factor;
+        let samples: Vec<m::Vector> = (0..candidate_count)
+            .map(|_| self.sampler.sample(rng).position)
+            .collect();
+
+        let total_area: f32 = self.sampler.triangles.iter().map(|t| t.area).sum();
+        let r_max = (total_area / (2.0 * 3f32.sqrt() * target_n as f32)).sqrt();
+        let r_min = r_max * (1.0 - (target_n as f32 / candidate_count as f32).powf(1.5)) * 0.65;
+        let weight_of_dist = |d: f32| -> f32 {
+            let d = d.clamp(r_min, 2.0 * r_max);
+            (1.0 - d / (2.0 * r_max)).powi(8)
+        };
+
+        let cell_size = 2.0 * r_max;
+        let mut grid: SpatialGrid<usize> = SpatialGrid::new(cell_size);
+        for (i, &p) in samples.iter().enumerate() {
+            grid.insert(p, i);
+        }
+        let neighbors_of = |i: usize| -> Vec<usize> {
+            grid.neighbors(samples[i])
+                .into_iter()
+                .filter_map(|(p, j)| {
+                    (j != i && m::dist_sq(samples[i], p) < cell_size * cell_size).then_some(j)
+                })
+                .collect()
+        };
+
+        let neighbor_lists: Vec<Vec<usize>> = (0..candidate_count).map(neighbors_of).collect();
+        let mut total_weight: Vec<f32> = (0..candidate_count)
+            .map(|i| {
+                neighbor_lists[i]
+                    .iter()
+                    .map(|&j| weight_of_dist(m::dist_sq(samples[i], samples[j]).sqrt()))
+                    .sum()
+            })
+            .collect();
+
+        let mut heap: BinaryHeap<HeapEntry> = total_weight
+            .iter()
+            .enumerate()
+            .map(|(index, &weight)| HeapEntry { weight, index })
+            .collect();
+        let mut removed = vec![false; candidate_count];
+        let mut remaining = candidate_count;
+
+        while remaining > target_n {
+            let Some(HeapEntry { weight, index }) = heap.pop() else {
+                break;
+            };
+            // Entries become stale once their weight has been decreased by a neighbor's
+            // elimination; only act on the freshest entry for an index.
+            if removed[index] || weight != total_weight[index] {
+                continue;
+            }
+            removed[index] = true;
+            remaining -= 1;
+            for &j in &neighbor_lists[index] {
+                if removed[j] {
+                    continue;
+                }
+                let d = m::dist_sq(samples[index], samples[j]).sqrt();
+                total_weight[j] -= weight_of_dist(d);
+                heap.push(HeapEntry {
+                    weight: total_weight[j],
+                    index: j,
+                });
+            }
+        }
+
+        samples
+            .into_iter()
+            .enumerate()
+            .filter(|&(i, _)| !removed[i])
+            .map(|(_, p)| p)
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct HeapEntry {
+    weight: f32,
+    index: usize,
+}
+
+impl Eq for HeapEntry {}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.weight.total_cmp(&other.weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn quad() -> (Vec<m::Vector>, Vec<[usize; 3]>) {
+        let verts = vec![
+            [0.0, 0.0, 0.0],
+            [50.0, 0.0, 0.0],
+            [50.0, 50.0, 0.0],
+            [0.0, 50.0, 0.0],
+        ];
+        let faces = vec![[0, 1, 2], [0, 2, 3]];
+        (verts, faces)
+    }
+
+    #[test]
+    fn sample_grid_respects_minimum_distance() {
+        let (verts, faces) = quad();
+        let r = 1.0;
+        let surf = PoissonDiskSurface::new_grid(r, &verts, &faces).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let points = surf.sample_grid(30, 2001, &mut rng);
+
+        assert!(points.len() > 100);
+        for (i, &p) in points.iter().enumerate() {
+            for &q in &points[i + 1..] {
+                assert!(
+                    m::dist_sq(p, q) >= r * r,
+                    "found a pair closer than r: dist = {}",
+                    m::dist_sq(p, q).sqrt()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sample_naive_errors_on_grid_built_sampler() {
+        let (verts, faces) = quad();
+        let surf = PoissonDiskSurface::new_grid(1.0, &verts, &faces).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+
+        assert!(surf.sample_naive(10, 10, &mut rng).is_err());
+    }
+
+    #[test]
+    fn sample_eliminated_returns_target_n_with_blue_noise_spacing() {
+        let (verts, faces) = quad();
+        let surf = PoissonDiskSurface::new_grid(1.0, &verts, &faces).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+
+        let target_n = 200;
+        let points = surf.sample_eliminated(target_n, 10, &mut rng);
+        assert_eq!(points.len(), target_n);
+
+        // quad() is a 50x50 square, so its total surface area is fixed; r_max is the
+        // same blue-noise radius sample_eliminated derives from it internally.
+        let total_area = 50.0 * 50.0_f32;
+        let r_max = (total_area / (2.0 * 3f32.sqrt() * target_n as f32)).sqrt();
+        let min_dist = points
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &p)| points[i + 1..].iter().map(move |&q| m::dist_sq(p, q).sqrt()))
+            .fold(f32::MAX, f32::min);
+
+        // Weighted sample elimination only approximates a Poisson-disk minimum
+        // separation, so leave generous margin below the r_max blue-noise radius rather
+        // than asserting an exact bound.
+        assert!(
+            min_dist > r_max * 0.5,
+            "closest surviving pair ({min_dist}) fell well below the target spacing ({r_max})"
+        );
+    }
 }
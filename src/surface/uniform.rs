@@ -2,7 +2,7 @@ use crate::vecmath as m;
 use rand_distr::weighted_alias::WeightedAliasIndex;
 use rand_distr::Distribution;
 
-use super::{vert_ids_to_pos, MeshRandError, SurfSample, Triangle};
+use super::{vert_ids_to, vert_ids_to_pos, MeshRandError, SurfSample, Triangle};
 
 /// A distribution for sampling points uniformly on the surface of a 3d model
 ///
@@ -23,12 +23,18 @@ use super::{vert_ids_to_pos, MeshRandError, SurfSample, Triangle};
 ///     [1.0, 2.0, 0.0],
 /// ];
 /// let faces = [[0, 1, 2], [0, 1, 3]];
-/// let mesh_dist = UniformSurface::new(&verticies, &faces)?;
+/// let normals = [
+///     [1.0, 0.0, 0.0],
+///     [0.0, 1.0, 0.0],
+///     [0.0, 0.0, 1.0],
+///     [1.0, 2.0, 0.0],
+/// ];
+/// let mesh_dist = UniformSurface::new(&verticies, &faces, Some(&normals), None)?;
 /// let mut rng = rand::thread_rng();
 /// let sample = mesh_dist.sample(&mut rng);
 /// println!(
-///     "generated point on mesh at {:?} located on face with index {:?} with normal {:?}",
-///     sample.position, sample.face_index, sample.normal
+///     "generated point on mesh at {:?} with barycentric weights {:?} and shading normal {:?}",
+///     sample.position, sample.barycentric, sample.normal
 /// );
 /// # Ok(())
 /// # }
@@ -41,20 +47,32 @@ pub struct UniformSurface {
 }
 
 impl UniformSurface {
-    /// Initializes a new mesh surface distribution given verticies and faces (triangles)
+    /// Initializes a new mesh surface distribution given verticies and faces (triangles).
+    ///
+    /// `normals` and `uvs`, when supplied, are per-vertex attribute arrays indexed the same
+    /// way as `verts`; each [SurfSample] then carries a shading normal and texture
+    /// coordinate interpolated from them at the sampled barycentric coordinates.
     ///
     /// # Result
     /// Returns an error if:
-    /// * An index defining a face is out of range of the verticies collection
+    /// * An index defining a face is out of range of the verticies (or `normals`/`uvs`) collection
     // * The area of one of the triangles provided is very close to 0 (`f32::is_normal(area) == false`)
     /// * The collection of faces is empty
-    pub fn new(verts: &[m::Vector], faces: &[[usize; 3]]) -> Result<Self, MeshRandError> {
+    pub fn new(
+        verts: &[m::Vector],
+        faces: &[[usize; 3]],
+        normals: Option<&[m::Vector]>,
+        uvs: Option<&[[f32; 2]]>,
+    ) -> Result<Self, MeshRandError> {
         let mut triangles = Vec::with_capacity(faces.len());
         let mut triangle_areas = Vec::with_capacity(faces.len());
 
         for face in faces {
             let [p1, p2, p3] = vert_ids_to_pos(face, verts)?;
-            let Ok(triangle) = Triangle::from_points(p1, p2, p3) else {
+            let vertex_normals = normals.map(|n| vert_ids_to(face, n)).transpose()?;
+            let vertex_uvs = uvs.map(|uv| vert_ids_to(face, uv)).transpose()?;
+            let Ok(triangle) = Triangle::from_points(p1, p2, p3, vertex_normals, vertex_uvs)
+            else {
                 continue;
             };
             triangle_areas.push(triangle.area);
@@ -71,18 +89,131 @@ impl UniformSurface {
             triangle_dist,
         })
     }
+
+    /// Draws approximately `n` samples using stratified, area-proportional sampling.
+    ///
+    /// Rather than picking each triangle independently from the area-weighted alias
+    /// table, every triangle is visited once and assigned `floor(n * area / total_area)`
+    /// guaranteed samples, plus one extra sample with probability equal to the
+    /// fractional remainder (stochastic rounding). Each allotted sample is then drawn
+    /// uniformly within its triangle. This keeps the expected sample count exactly `n`
+    /// while producing far less density variance than `n` independent calls to
+    /// [`sample`](Self::sample).
+    pub fn sample_stratified<R>(&self, n: usize, rng: &mut R) -> Vec<SurfSample>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        let total_area: f32 = self.triangles.iter().map(|t| t.area).sum();
+        let mut samples = Vec::with_capacity(n);
+        for (t_index, triangle) in self.triangles.iter().enumerate() {
+            let expected = n as f32 * triangle.area / total_area;
+            let mut k = expected.floor();
+            if rng.gen_range(0.0..1.0) < expected - k {
+                k += 1.0;
+            }
+            for _ in 0..(k as usize) {
+                samples.push(triangle.sample_surf(t_index, rng));
+            }
+        }
+        samples
+    }
+
+    /// Draws `n` samples in parallel across a rayon thread pool.
+    ///
+    /// `n` is split into fixed-size chunks of 4096 samples, each seeded from an
+    /// independent `u64` drawn from `rng`, so the result is deterministic for a given
+    /// seed regardless of how many threads rayon happens to run with. `triangles` and
+    /// `triangle_dist` are read-only and `Send + Sync`, so each worker independently
+    /// picks triangles and samples points with no synchronization beyond the initial
+    /// seed draw.
+    #[cfg(feature = "rayon")]
+    pub fn sample_batch<R>(&self, n: usize, rng: &mut R) -> Vec<SurfSample>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        use rand::SeedableRng;
+        use rayon::prelude::*;
+
+        const CHUNK_SIZE: usize = 4096;
+
+        let seeds: Vec<u64> = (0..n.div_ceil(CHUNK_SIZE)).map(|_| rng.gen()).collect();
+
+        seeds
+            .into_par_iter()
+            .enumerate()
+            .flat_map(|(chunk_ind, seed)| {
+                let mut chunk_rng = rand::rngs::StdRng::seed_from_u64(seed);
+                let start = chunk_ind * CHUNK_SIZE;
+                let count = CHUNK_SIZE.min(n - start);
+                (0..count)
+                    .map(|_| {
+                        let t_ind = self.triangle_dist.sample(&mut chunk_rng);
+                        self.triangles[t_ind].sample_surf(t_ind, &mut chunk_rng)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
 }
 
 impl Distribution<SurfSample> for UniformSurface {
     /// Samples the model surface uniformly, returning an instance of the [SurfSample] struct
     fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> SurfSample {
         let t_ind = self.triangle_dist.sample(rng);
-        let triangle = self.triangles[t_ind];
-        let point = triangle.sample(rng);
-        SurfSample {
-            position: point,
-            t_index: t_ind,
-            triangle,
-        }
+        self.triangles[t_ind].sample_surf(t_ind, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn sample_stratified_hits_exact_count_on_equal_area_triangles() {
+        // A unit square split into 4 equal-area triangles: n divisible by 4 makes every
+        // triangle's expected allotment (n * area / total_area) an exact integer, so
+        // stochastic rounding never triggers and the total is pinned down exactly.
+        let verts = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.5, 0.5, 0.0],
+        ];
+        let faces = [[0, 1, 4], [1, 2, 4], [2, 3, 4], [3, 0, 4]];
+        let surf = UniformSurface::new(&verts, &faces, None, None).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+
+        let samples = surf.sample_stratified(400, &mut rng);
+
+        assert_eq!(samples.len(), 400);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn sample_batch_is_deterministic_across_thread_counts() {
+        let verts = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+        ];
+        let faces = [[0, 1, 2], [1, 3, 2]];
+        let surf = UniformSurface::new(&verts, &faces, None, None).unwrap();
+
+        let positions = |pool_size| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(pool_size)
+                .build()
+                .unwrap();
+            pool.install(|| surf.sample_batch(10_000, &mut rng))
+                .into_iter()
+                .map(|s| s.position)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(positions(1), positions(4));
     }
 }